@@ -0,0 +1,149 @@
+use amethyst::core::{timing::Time, Transform};
+use amethyst::ecs::{Component, DenseVecStorage, Join, Read, ReadStorage, System, WriteStorage};
+
+use crate::net::NetplayActive;
+use crate::pong::{Ball, Paddle, ARENA_HEIGHT};
+use crate::settings::Settings;
+use crate::states::Paused;
+
+/// Attached to the `Paddle` the computer should drive, so `AiPaddleSystem`
+/// knows which entity to steer instead of reading the `left_paddle`/
+/// `right_paddle` input axes for it.
+#[derive(Default)]
+pub struct AiPaddle;
+
+impl Component for AiPaddle {
+    type Storage = DenseVecStorage<Self>;
+}
+
+/// Reaction delay and speed cap for the computer-controlled paddle. Lives
+/// on `Settings` so it's persisted to `settings.ron` and a difficulty
+/// picker can tune it without touching code.
+#[derive(Debug, Clone, Copy, PartialEq, serde::Serialize, serde::Deserialize)]
+pub struct AiDifficulty {
+    /// Seconds between target-position updates. Higher values mean the AI
+    /// keeps steering toward a staler prediction, simulating slower
+    /// reflexes rather than a less accurate read of the ball.
+    pub reaction_delay: f32,
+    /// Maximum paddle speed, in the same units/second as `Ball::velocity`.
+    pub max_speed: f32,
+}
+
+impl Default for AiDifficulty {
+    fn default() -> Self {
+        AiDifficulty {
+            reaction_delay: 0.2,
+            max_speed: 60.0,
+        }
+    }
+}
+
+/// Drives any `Paddle` tagged `AiPaddle` toward a predicted ball position
+/// instead of reading input axes.
+///
+/// Every `reaction_delay` seconds it re-predicts where the ball will cross
+/// the paddle's X by extrapolating the ball's current velocity and
+/// reflecting the projection off the top/bottom arena walls the same way
+/// `BounceSystem` reflects the real ball, then steers toward that target
+/// the rest of the time at a rate capped by `max_speed`.
+pub struct AiPaddleSystem {
+    reaction_timer: f32,
+    target_y: f32,
+}
+
+impl Default for AiPaddleSystem {
+    fn default() -> Self {
+        AiPaddleSystem {
+            reaction_timer: 0.0,
+            target_y: ARENA_HEIGHT / 2.0,
+        }
+    }
+}
+
+impl<'s> System<'s> for AiPaddleSystem {
+    type SystemData = (
+        WriteStorage<'s, Transform>,
+        ReadStorage<'s, Paddle>,
+        ReadStorage<'s, AiPaddle>,
+        ReadStorage<'s, Ball>,
+        Read<'s, Time>,
+        Read<'s, Paused>,
+        Read<'s, NetplayActive>,
+        Read<'s, Settings>,
+    );
+
+    fn run(
+        &mut self,
+        (mut transforms, paddles, ai_paddles, balls, time, paused, netplay, settings): Self::SystemData,
+    ) {
+        if paused.0 || netplay.0 {
+            return;
+        }
+
+        let difficulty = settings.ai_difficulty;
+
+        self.reaction_timer -= time.delta_seconds();
+        if self.reaction_timer <= 0.0 {
+            self.reaction_timer = difficulty.reaction_delay;
+
+            if let Some(ai_transform) = (&transforms, &ai_paddles).join().next().map(|(t, _)| t) {
+                let paddle_x = ai_transform.translation().x;
+
+                if let Some((ball, ball_transform)) = (&balls, &transforms).join().next() {
+                    self.target_y = predict_ball_y(
+                        ball,
+                        ball_transform.translation().x,
+                        ball_transform.translation().y,
+                        paddle_x,
+                    );
+                }
+            }
+        }
+
+        let max_step = difficulty.max_speed * time.delta_seconds();
+        for (paddle, _, transform) in (&paddles, &ai_paddles, &mut transforms).join() {
+            let paddle_y = transform.translation().y;
+            let step = (self.target_y - paddle_y).clamp(-max_step, max_step);
+            transform.set_translation_y(
+                (paddle_y + step)
+                    .min(ARENA_HEIGHT - paddle.height * 0.5)
+                    .max(paddle.height * 0.5),
+            );
+        }
+    }
+}
+
+/// Extrapolates straight-line ball travel from `(ball_x, ball_y)` at
+/// `ball.velocity` out to `target_x`, reflecting the projected Y off the
+/// top/bottom arena walls every time it would cross one.
+fn predict_ball_y(ball: &Ball, ball_x: f32, ball_y: f32, target_x: f32) -> f32 {
+    if ball.velocity[0] == 0.0 {
+        return ball_y;
+    }
+
+    let travel_time = (target_x - ball_x) / ball.velocity[0];
+    if travel_time <= 0.0 {
+        // Ball is moving away from this paddle; hold the last prediction
+        // instead of extrapolating backwards in time.
+        return ball_y;
+    }
+
+    let span = ARENA_HEIGHT - 2.0 * ball.radius;
+    if span <= 0.0 {
+        return ball_y;
+    }
+
+    let unwrapped = ball_y + ball.velocity[1] * travel_time;
+
+    // Fold the unwrapped Y back into [radius, ARENA_HEIGHT - radius] as a
+    // triangle wave, one reflection per wall crossing.
+    let mut relative = (unwrapped - ball.radius) % (2.0 * span);
+    if relative < 0.0 {
+        relative += 2.0 * span;
+    }
+    if relative > span {
+        relative = 2.0 * span - relative;
+    }
+
+    relative + ball.radius
+}
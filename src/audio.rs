@@ -0,0 +1,234 @@
+use std::collections::HashMap;
+use std::{iter::Cycle, vec::IntoIter};
+
+use amethyst::assets::{AssetStorage, Loader};
+use amethyst::audio::{output::Output, AudioSink, OggFormat, Source, SourceHandle};
+use amethyst::ecs::{Read, ReadExpect, System, World, WorldExt, WriteExpect};
+
+use crate::settings::Settings;
+
+const BOUNCE_SOUND: &str = "audio/bounce.ogg";
+const SCORE_SOUND: &str = "audio/score.ogg";
+
+/// Named groups of OGG tracks that can be swapped at runtime. Each track
+/// carries its own relative volume (multiplied with `Settings::music_volume`
+/// by [`MusicVolumeSystem`]) so a quieter track doesn't have to be
+/// re-mastered to match a louder neighbour in the same soundtrack, and the
+/// soundtrack as a whole says whether it loops back to its first track or
+/// plays through once and falls silent.
+const SOUNDTRACKS: &[(&str, &[(&str, f32)], bool)] = &[
+    (
+        "menu",
+        &[("audio/Computer_Music_All-Stars_-_Wheres_My_Jetpack.ogg", 0.6)],
+        true,
+    ),
+    (
+        "gameplay",
+        &[
+            ("audio/Computer_Music_All-Stars_-_Wheres_My_Jetpack.ogg", 1.0),
+            ("audio/Computer_Music_All-Stars_-_Albatross_v2.ogg", 0.8),
+        ],
+        true,
+    ),
+    (
+        "game_over",
+        &[("audio/Computer_Music_All-Stars_-_Albatross_v2.ogg", 0.8)],
+        false,
+    ),
+];
+
+const DEFAULT_SOUNDTRACK: &str = "menu";
+
+pub struct Sounds {
+    pub score_sfx: SourceHandle,
+    pub bounce_sfx: SourceHandle,
+}
+
+struct SoundtrackEntry {
+    tracks: Vec<(SourceHandle, f32)>,
+    looping: bool,
+}
+
+/// Either repeats its tracks forever or plays through them once and then
+/// yields `None` forever after, depending on the soundtrack's `looping`
+/// flag.
+enum TrackCycle {
+    Looping(Cycle<IntoIter<(SourceHandle, f32)>>),
+    Once(IntoIter<(SourceHandle, f32)>),
+}
+
+impl Iterator for TrackCycle {
+    type Item = (SourceHandle, f32);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        match self {
+            TrackCycle::Looping(iter) => iter.next(),
+            TrackCycle::Once(iter) => iter.next(),
+        }
+    }
+}
+
+/// Feeds the currently selected soundtrack's tracks to `DjSystem` one at a
+/// time via [`Music::next_track`]. Holds every soundtrack's decoded OGG
+/// handles up front so switching with [`Music::set_soundtrack`] just
+/// rebuilds the iterator rather than reloading anything.
+pub struct Music {
+    music: TrackCycle,
+    table: HashMap<String, SoundtrackEntry>,
+    /// Relative volume of the track `next_track` most recently handed to
+    /// `DjSystem`. Applied to `AudioSink` every frame by
+    /// [`MusicVolumeSystem`], so per-track volume takes effect as soon as
+    /// `DjSystem` advances rather than only when the soundtrack changes.
+    current_track_volume: f32,
+}
+
+impl Music {
+    /// Switches the `DjSystem`'s playlist to the named soundtrack. Falls
+    /// back to silence (an empty, already-exhausted cycle) if `name` isn't
+    /// in the table.
+    pub fn set_soundtrack(&mut self, name: &str) {
+        match self.table.get(name) {
+            Some(entry) => {
+                self.current_track_volume =
+                    entry.tracks.first().map(|(_, volume)| *volume).unwrap_or(1.0);
+                self.music = if entry.looping {
+                    TrackCycle::Looping(entry.tracks.clone().into_iter().cycle())
+                } else {
+                    TrackCycle::Once(entry.tracks.clone().into_iter())
+                };
+            }
+            None => {
+                self.current_track_volume = 1.0;
+                self.music = TrackCycle::Once(Vec::new().into_iter());
+            }
+        }
+    }
+
+    /// The `DjSystemDesc` closure's hook into the current soundtrack: hands
+    /// back the next track to play and records its volume for
+    /// [`MusicVolumeSystem`] to apply.
+    pub(crate) fn next_track(&mut self) -> Option<SourceHandle> {
+        let (handle, volume) = self.music.next()?;
+        self.current_track_volume = volume;
+        Some(handle)
+    }
+
+    fn current_track_volume(&self) -> f32 {
+        self.current_track_volume
+    }
+}
+
+fn load_audio_track(loader: &Loader, world: &World, file: &str) -> SourceHandle {
+    loader.load(file, OggFormat, (), &world.read_resource())
+}
+
+/// Loads the sound effects and every soundtrack's tracks, then inserts them
+/// as world resources with the `"menu"` soundtrack selected. A no-op if
+/// `Music` is already present, so `MainMenuState`, `Pong` and
+/// `NetPongState` can all call this unconditionally on `on_start` without
+/// re-decoding every OGG on each menu/play transition.
+pub fn initialize_audio(world: &mut World) {
+    if world.try_fetch::<Music>().is_some() {
+        return;
+    }
+
+    let (sound_effects, music) = {
+        let loader = world.read_resource::<Loader>();
+        let music_volume = world.read_resource::<Settings>().music_volume;
+
+        let mut sink = world.write_resource::<AudioSink>();
+
+        let table: HashMap<String, SoundtrackEntry> = SOUNDTRACKS
+            .iter()
+            .map(|(name, tracks, looping)| {
+                let tracks = tracks
+                    .iter()
+                    .map(|(file, volume)| (load_audio_track(&loader, world, file), *volume))
+                    .collect();
+                (
+                    name.to_string(),
+                    SoundtrackEntry {
+                        tracks,
+                        looping: *looping,
+                    },
+                )
+            })
+            .collect();
+
+        let mut music = Music {
+            music: TrackCycle::Once(Vec::new().into_iter()),
+            table,
+            current_track_volume: 1.0,
+        };
+        music.set_soundtrack(DEFAULT_SOUNDTRACK);
+        sink.set_volume(music_volume * music.current_track_volume());
+
+        let sound = Sounds {
+            bounce_sfx: load_audio_track(&loader, world, BOUNCE_SOUND),
+            score_sfx: load_audio_track(&loader, world, SCORE_SOUND),
+        };
+
+        (sound, music)
+    };
+
+    world.insert(sound_effects);
+    world.insert(music);
+}
+
+/// Switches to the named soundtrack and re-applies the sink volume for its
+/// first track, scaled by `Settings::music_volume`. States call this on
+/// transition (e.g. `MainMenuState` selects `"menu"`, `Pong` selects
+/// `"gameplay"`).
+pub fn select_soundtrack(world: &mut World, name: &str) {
+    let music_volume = world.read_resource::<Settings>().music_volume;
+    let mut music = world.write_resource::<Music>();
+    music.set_soundtrack(name);
+
+    let volume = music_volume * music.current_track_volume();
+    drop(music);
+    world.write_resource::<AudioSink>().set_volume(volume);
+}
+
+/// Keeps `AudioSink`'s volume matching the currently-playing track's
+/// per-track volume (scaled by `Settings::music_volume`) every frame, so a
+/// `DjSystem` advance partway through a soundtrack picks up its new
+/// track's volume without waiting for another `select_soundtrack` call.
+pub struct MusicVolumeSystem;
+
+impl<'s> System<'s> for MusicVolumeSystem {
+    type SystemData = (
+        ReadExpect<'s, Music>,
+        Read<'s, Settings>,
+        WriteExpect<'s, AudioSink>,
+    );
+
+    fn run(&mut self, (music, settings, mut sink): Self::SystemData) {
+        sink.set_volume(settings.music_volume * music.current_track_volume());
+    }
+}
+
+pub fn play_bounce_sound(
+    sounds: &Sounds,
+    storage: &AssetStorage<Source>,
+    output: Option<&Output>,
+    volume: f32,
+) {
+    if let Some(output) = output {
+        if let Some(sound) = storage.get(&sounds.bounce_sfx) {
+            output.play_once(sound, volume);
+        }
+    }
+}
+
+pub fn play_score_sound(
+    sounds: &Sounds,
+    storage: &AssetStorage<Source>,
+    output: Option<&Output>,
+    volume: f32,
+) {
+    if let Some(output) = output {
+        if let Some(sound) = storage.get(&sounds.score_sfx) {
+            output.play_once(sound, volume);
+        }
+    }
+}
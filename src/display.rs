@@ -0,0 +1,208 @@
+use amethyst::ecs::{Read, ReadExpect, System, World, Write};
+use amethyst::input::{InputEvent, StringBindings, VirtualKeyCode};
+use amethyst::shrev::{EventChannel, ReaderId};
+use amethyst::window::Window;
+
+use crate::settings::{Settings, SettingsPath};
+
+/// A resolution + refresh-rate + fullscreen/vsync choice. Lives on
+/// `Settings` so it's persisted to `settings.ron` and restored on the next
+/// launch.
+#[derive(Debug, Clone, Copy, PartialEq, serde::Serialize, serde::Deserialize)]
+pub struct DisplaySettings {
+    pub width: u32,
+    pub height: u32,
+    pub refresh_rate: u32,
+    pub fullscreen: bool,
+    /// Whether the swapchain should wait for vertical blank. Stored and
+    /// persisted to `settings.ron`, but **not yet applied**: present-mode
+    /// selection happens inside `RenderingBundle`'s gfx-hal backend, and
+    /// `DisplayConfig`/`RenderToWindow` don't expose a hook for choosing it
+    /// in this amethyst version. Surface a toggle here anyway so a settings
+    /// screen has something to bind to once that hook lands.
+    pub vsync: bool,
+}
+
+impl Default for DisplaySettings {
+    fn default() -> Self {
+        DisplaySettings {
+            width: 500,
+            height: 500,
+            refresh_rate: 60,
+            fullscreen: false,
+            vsync: true,
+        }
+    }
+}
+
+/// One resolution + refresh-rate combination the active monitor supports.
+#[derive(Debug, Clone, Copy)]
+pub struct VideoMode {
+    pub width: u32,
+    pub height: u32,
+    pub refresh_rate: u32,
+}
+
+/// Lists the modes the current monitor supports, for a settings screen to
+/// offer. Empty if no monitor could be queried (e.g. headless CI).
+pub fn enumerate_display_modes(window: &Window) -> Vec<VideoMode> {
+    window
+        .current_monitor()
+        .map(|monitor| {
+            monitor
+                .video_modes()
+                .map(|mode| {
+                    let size = mode.size();
+                    VideoMode {
+                        width: size.width,
+                        height: size.height,
+                        refresh_rate: mode.refresh_rate(),
+                    }
+                })
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+/// Applies `settings`'s resolution, refresh rate, and fullscreen choice to
+/// `window`. Fullscreen picks an *exclusive* video mode matching
+/// `width`/`height`/`refresh_rate` when the monitor offers one, so the
+/// refresh rate actually takes effect instead of just the resolution;
+/// falls back to borderless at the monitor's native mode if no exact match
+/// exists (e.g. the settings came from a different monitor). Windowed mode
+/// just resizes the window — winit has no refresh-rate control outside
+/// exclusive fullscreen.
+pub fn apply_display_mode(window: &Window, settings: &DisplaySettings) {
+    if settings.fullscreen {
+        let exclusive = window.current_monitor().and_then(|monitor| {
+            monitor.video_modes().find(|mode| {
+                let size = mode.size();
+                size.width == settings.width
+                    && size.height == settings.height
+                    && mode.refresh_rate() == settings.refresh_rate
+            })
+        });
+        match exclusive {
+            Some(mode) => {
+                window.set_fullscreen(Some(amethyst::winit::window::Fullscreen::Exclusive(mode)))
+            }
+            None => {
+                let monitor = window.current_monitor();
+                window.set_fullscreen(monitor.map(amethyst::winit::window::Fullscreen::Borderless));
+            }
+        }
+    } else {
+        window.set_fullscreen(None);
+        window.set_inner_size(amethyst::winit::dpi::PhysicalSize::new(
+            settings.width,
+            settings.height,
+        ));
+    }
+}
+
+/// Toggles borderless fullscreen on F11 and keeps `Settings::display` (and
+/// therefore `settings.ron`) in sync with the result.
+#[derive(Default)]
+pub struct FullscreenToggleSystem {
+    reader_id: Option<ReaderId<InputEvent<StringBindings>>>,
+}
+
+impl<'s> System<'s> for FullscreenToggleSystem {
+    type SystemData = (
+        Read<'s, EventChannel<InputEvent<StringBindings>>>,
+        Write<'s, Settings>,
+        ReadExpect<'s, SettingsPath>,
+        ReadExpect<'s, Window>,
+    );
+
+    fn setup(&mut self, world: &mut World) {
+        use amethyst::ecs::SystemData;
+        Self::SystemData::setup(world);
+        self.reader_id = Some(
+            world
+                .fetch_mut::<EventChannel<InputEvent<StringBindings>>>()
+                .register_reader(),
+        );
+    }
+
+    fn run(&mut self, (events, mut settings, settings_path, window): Self::SystemData) {
+        let reader_id = self
+            .reader_id
+            .as_mut()
+            .expect("FullscreenToggleSystem::setup was not called");
+
+        for event in events.read(reader_id) {
+            if let InputEvent::KeyPressed {
+                key_code: VirtualKeyCode::F11,
+                ..
+            } = event
+            {
+                settings.display.fullscreen = !settings.display.fullscreen;
+                apply_display_mode(&window, &settings.display);
+                settings.save(&settings_path.0);
+            }
+        }
+    }
+}
+
+/// Cycles to the active monitor's next supported resolution/refresh-rate
+/// combination on F10 (wrapping back to the first after the last), applies
+/// it immediately, and persists the choice the same way
+/// `FullscreenToggleSystem` does for fullscreen — so a mode picked at
+/// runtime survives a restart instead of reverting to `config/display.ron`.
+#[derive(Default)]
+pub struct DisplayModeToggleSystem {
+    reader_id: Option<ReaderId<InputEvent<StringBindings>>>,
+}
+
+impl<'s> System<'s> for DisplayModeToggleSystem {
+    type SystemData = (
+        Read<'s, EventChannel<InputEvent<StringBindings>>>,
+        Write<'s, Settings>,
+        ReadExpect<'s, SettingsPath>,
+        ReadExpect<'s, Window>,
+    );
+
+    fn setup(&mut self, world: &mut World) {
+        use amethyst::ecs::SystemData;
+        Self::SystemData::setup(world);
+        self.reader_id = Some(
+            world
+                .fetch_mut::<EventChannel<InputEvent<StringBindings>>>()
+                .register_reader(),
+        );
+    }
+
+    fn run(&mut self, (events, mut settings, settings_path, window): Self::SystemData) {
+        let reader_id = self
+            .reader_id
+            .as_mut()
+            .expect("DisplayModeToggleSystem::setup was not called");
+
+        for event in events.read(reader_id) {
+            if let InputEvent::KeyPressed {
+                key_code: VirtualKeyCode::F10,
+                ..
+            } = event
+            {
+                let modes = enumerate_display_modes(&window);
+                if modes.is_empty() {
+                    continue;
+                }
+                let current = modes.iter().position(|mode| {
+                    mode.width == settings.display.width
+                        && mode.height == settings.display.height
+                        && mode.refresh_rate == settings.display.refresh_rate
+                });
+                let next = current.map_or(0, |current| (current + 1) % modes.len());
+
+                let mode = modes[next];
+                settings.display.width = mode.width;
+                settings.display.height = mode.height;
+                settings.display.refresh_rate = mode.refresh_rate;
+                apply_display_mode(&window, &settings.display);
+                settings.save(&settings_path.0);
+            }
+        }
+    }
+}
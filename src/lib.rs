@@ -0,0 +1,225 @@
+//! Startup logic shared between the native binary (`main.rs`) and the
+//! `wasm32-unknown-unknown` groundwork in [`wasm_prep`]. Both hosts call
+//! [`run`] so they build the exact same `GameData` dispatcher; only how
+//! they get an `app_root` and how panics/logging are routed differs.
+//!
+//! [`wasm_prep`] is **not** a working browser target — it's deferred prep,
+//! named and gated so it can't be mistaken for shipped wasm32 support. See
+//! its module doc for what's missing and what landing those pieces would
+//! take.
+
+use amethyst::{
+    audio::{AudioBundle, DjSystemDesc},
+    core::transform::TransformBundle,
+    input::{InputBundle, StringBindings},
+    prelude::*,
+    renderer::{
+        plugins::{RenderFlat2D, RenderToWindow},
+        types::DefaultBackend,
+        RenderingBundle,
+    },
+    ui::{RenderUi, UiBundle},
+    window::DisplayConfig,
+};
+use std::net::SocketAddr;
+use std::path::PathBuf;
+
+mod ai;
+mod audio;
+mod display;
+mod net;
+mod pong;
+mod settings;
+mod states;
+mod systems;
+
+use crate::audio::Music;
+use crate::net::NetPongState;
+use crate::pong::{GameMode, Side};
+use crate::settings::{Settings, SettingsPath};
+use crate::states::MainMenuState;
+
+/// Addresses and side to start directly into `NetPongState` with, bypassing
+/// the main menu. Built by `main()` from CLI arguments (there is no
+/// in-menu netplay picker yet — two machines need each other's address
+/// before a session can connect, which doesn't fit the keyboard-only menu
+/// flow) and passed to [`run`].
+pub struct NetplayArgs {
+    pub bind_addr: SocketAddr,
+    pub remote_addr: SocketAddr,
+    /// `true` to play `Side::Left`, `false` for `Side::Right`.
+    pub host: bool,
+}
+
+/// Bridges `MainMenuState` and `NetPongState` so [`run`] can hand
+/// `Application::build` a single initial state whichever of the two it
+/// picked based on `NetplayArgs`.
+enum InitialState {
+    Menu(MainMenuState),
+    Netplay(NetPongState),
+}
+
+impl SimpleState for InitialState {
+    fn on_start(&mut self, data: StateData<'_, GameData<'_, '_>>) {
+        match self {
+            InitialState::Menu(state) => state.on_start(data),
+            InitialState::Netplay(state) => state.on_start(data),
+        }
+    }
+
+    fn on_stop(&mut self, data: StateData<'_, GameData<'_, '_>>) {
+        match self {
+            InitialState::Menu(state) => state.on_stop(data),
+            InitialState::Netplay(state) => state.on_stop(data),
+        }
+    }
+
+    fn handle_event(
+        &mut self,
+        data: StateData<'_, GameData<'_, '_>>,
+        event: StateEvent,
+    ) -> SimpleTrans {
+        match self {
+            InitialState::Menu(state) => state.handle_event(data, event),
+            InitialState::Netplay(state) => state.handle_event(data, event),
+        }
+    }
+
+    fn update(&mut self, data: &mut StateData<'_, GameData<'_, '_>>) -> SimpleTrans {
+        match self {
+            InitialState::Menu(state) => state.update(data),
+            InitialState::Netplay(state) => state.update(data),
+        }
+    }
+}
+
+/// Builds and runs the `Application` rooted at `app_root`. Called by
+/// `main()` with `amethyst::utils::application_root_dir()` natively, and
+/// by [`wasm::run_wasm`] with a fixed root in the browser. `netplay`, when
+/// given, starts the game directly into `NetPongState` instead of the main
+/// menu.
+pub fn run(app_root: PathBuf, netplay: Option<NetplayArgs>) -> amethyst::Result<()> {
+    let binding_path = app_root.join("config").join("bindings.ron");
+    let display_config_path = app_root.join("config").join("display.ron");
+    let settings_path = app_root.join("settings.ron");
+    let input_bundle =
+        InputBundle::<StringBindings>::new().with_bindings_from_file(binding_path)?;
+
+    let settings = Settings::load_or_default(&settings_path);
+
+    // The window's initial size comes from `Settings::display` rather than
+    // `config/display.ron` directly, so a resolution picked in-game is
+    // honoured again on the next launch.
+    let mut display_config = DisplayConfig::load(&display_config_path)?;
+    display_config.dimensions = Some((settings.display.width, settings.display.height));
+
+    let game_data = GameDataBuilder::default()
+        // Bundles
+        .with_bundle(input_bundle)?
+        .with_bundle(AudioBundle::default())?
+        .with_bundle(TransformBundle::new())?
+        .with_bundle(UiBundle::<StringBindings>::new())?
+        .with_bundle(
+            RenderingBundle::<DefaultBackend>::new()
+                .with_plugin(
+                    RenderToWindow::from_config(display_config)
+                        .with_clear([0.0, 0.0, 0.0, 1.0]),
+                )
+                .with_plugin(RenderFlat2D::default())
+                .with_plugin(RenderUi::default()),
+        )?
+        // Systems
+        .with_system_desc(
+            DjSystemDesc::new(|music: &mut Music| music.next_track()),
+            "dj_system",
+            &[],
+        )
+        .with(
+            audio::MusicVolumeSystem,
+            "music_volume_system",
+            &["dj_system"],
+        )
+        .with(systems::PaddleSystem, "paddle_system", &["input_system"])
+        .with(
+            ai::AiPaddleSystem::default(),
+            "ai_paddle_system",
+            &["paddle_system"],
+        )
+        .with(systems::MoveBallsSystem, "ball_system", &[])
+        .with(systems::WinnerSystem, "winner_system", &["ball_system"])
+        .with(
+            systems::BounceSystem,
+            "collision_system",
+            &["paddle_system", "ball_system"],
+        )
+        .with(
+            display::FullscreenToggleSystem::default(),
+            "fullscreen_toggle_system",
+            &["input_system"],
+        )
+        .with(
+            display::DisplayModeToggleSystem::default(),
+            "display_mode_toggle_system",
+            &["input_system"],
+        );
+
+    let initial_state = match netplay {
+        Some(args) => {
+            let local_player = if args.host { Side::Left } else { Side::Right };
+            InitialState::Netplay(NetPongState::new(args.bind_addr, args.remote_addr, local_player))
+        }
+        None => InitialState::Menu(MainMenuState::default()),
+    };
+
+    let assets_dir = app_root.join("assets");
+    let mut game = Application::build(assets_dir, initial_state)?
+        .with_resource(settings)
+        .with_resource(SettingsPath(settings_path))
+        .with_resource(GameMode::default())
+        .build(game_data)?;
+    game.run();
+    Ok(())
+}
+
+/// Deferred wasm32 groundwork — deliberately **not** a working browser
+/// target, and named/gated so it can't pass for one. `DefaultBackend`
+/// resolves to a native gfx-hal backend (Vulkan/Metal/DX12) with no
+/// `wasm32` target in this amethyst version, and asset loading still goes
+/// through `Loader`'s default filesystem `Source`; actually drawing to a
+/// canvas needs a WebGL/WebGPU rendering backend and an HTTP-backed
+/// `Source` that this engine doesn't ship yet. The `compile_error!` below
+/// fails the build rather than produce something that looks finished but
+/// can't draw anything — don't remove it until those land.
+///
+/// What's here is prep for once they do: one shared entry point
+/// ([`run`]), a console panic hook, and a host page (`web/index.html`)
+/// ready to load the module. `wasm_bindgen`/`web_sys`/
+/// `console_error_panic_hook` are declared here as if present in
+/// `Cargo.toml` under a `[target.'cfg(target_arch =
+/// "wasm32")'.dependencies]` section, matching how the rest of this crate
+/// is written against dependencies that can't be confirmed without a
+/// manifest.
+#[cfg(target_arch = "wasm32")]
+mod wasm_prep {
+    compile_error!(
+        "pong does not build for wasm32 yet: DefaultBackend has no wasm32 target and Loader's \
+         filesystem Source can't run in a browser. See the `wasm_prep` module doc on this \
+         crate's `lib.rs` for what needs to land first; don't remove this guard until it does."
+    );
+
+    use wasm_bindgen::prelude::*;
+
+    /// Browser entry point, invoked once the module is instantiated (see
+    /// `web/index.html`). `amethyst::start_logger` shells out to
+    /// `env_logger`, which has no terminal to write to in a browser, so
+    /// panics are routed to `console.error` instead and logging is simply
+    /// skipped.
+    #[wasm_bindgen(start)]
+    pub fn run_wasm() {
+        console_error_panic_hook::set_once();
+
+        if let Err(err) = super::run(std::path::PathBuf::from("/"), None) {
+            web_sys::console::error_1(&format!("{}", err).into());
+        }
+    }
+}
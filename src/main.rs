@@ -1,63 +1,52 @@
-use amethyst::{
-    audio::{AudioBundle, DjSystemDesc},
-    core::transform::TransformBundle,
-    input::{InputBundle, StringBindings},
-    prelude::*,
-    renderer::{
-        plugins::{RenderFlat2D, RenderToWindow},
-        types::DefaultBackend,
-        RenderingBundle,
-    },
-    ui::{RenderUi, UiBundle},
-    utils::application_root_dir,
-};
-
-mod audio;
-mod pong;
-mod systems;
-use crate::audio::Music;
-use crate::pong::Pong;
+use std::env;
+use std::net::SocketAddr;
+
+use amethyst::utils::application_root_dir;
+
+use pong::NetplayArgs;
 
 fn main() -> amethyst::Result<()> {
     amethyst::start_logger(Default::default());
-    let app_root = application_root_dir()?;
-    let binding_path = app_root.join("config").join("bindings.ron");
-    let display_config_path = app_root.join("config").join("display.ron");
-    let input_bundle =
-        InputBundle::<StringBindings>::new().with_bindings_from_file(binding_path)?;
-
-    let game_data = GameDataBuilder::default()
-        // Bundles
-        .with_bundle(input_bundle)?
-        .with_bundle(AudioBundle::default())?
-        .with_bundle(TransformBundle::new())?
-        .with_bundle(UiBundle::<StringBindings>::new())?
-        .with_bundle(
-            RenderingBundle::<DefaultBackend>::new()
-                .with_plugin(
-                    RenderToWindow::from_config_path(display_config_path)?
-                        .with_clear([0.0, 0.0, 0.0, 1.0]),
-                )
-                .with_plugin(RenderFlat2D::default())
-                .with_plugin(RenderUi::default()),
-        )?
-        // Systems
-        .with_system_desc(
-            DjSystemDesc::new(|music: &mut Music| music.music.next()),
-            "dj_system",
-            &[],
-        )
-        .with(systems::PaddleSystem, "paddle_system", &["input_system"])
-        .with(systems::MoveBallsSystem, "ball_system", &[])
-        .with(systems::WinnerSystem, "winner_system", &["ball_system"])
-        .with(
-            systems::BounceSystem,
-            "collision_system",
-            &["paddle_system", "ball_system"],
-        );
-
-    let assets_dir = app_root.join("assets");
-    let mut game = Application::new(assets_dir, Pong::default(), game_data)?;
-    game.run();
-    Ok(())
+
+    let netplay = match parse_netplay_args(env::args().skip(1).collect()) {
+        Ok(netplay) => netplay,
+        Err(usage) => {
+            eprintln!("{}", usage);
+            std::process::exit(1);
+        }
+    };
+
+    pong::run(application_root_dir()?, netplay)
+}
+
+/// Parses `pong netplay <bind-addr> <remote-addr> <host|client>` into the
+/// `NetplayArgs` `run` needs to start directly in `NetPongState`. A bare
+/// `pong` invocation (no arguments) returns `Ok(None)` for the normal
+/// single-machine game.
+fn parse_netplay_args(args: Vec<String>) -> Result<Option<NetplayArgs>, String> {
+    if args.is_empty() {
+        return Ok(None);
+    }
+
+    if args.len() != 4 || args[0] != "netplay" {
+        return Err("usage: pong netplay <bind-addr> <remote-addr> <host|client>".to_string());
+    }
+
+    let bind_addr: SocketAddr = args[1]
+        .parse()
+        .map_err(|err| format!("invalid bind address {:?}: {}", args[1], err))?;
+    let remote_addr: SocketAddr = args[2]
+        .parse()
+        .map_err(|err| format!("invalid remote address {:?}: {}", args[2], err))?;
+    let host = match args[3].as_str() {
+        "host" => true,
+        "client" => false,
+        other => return Err(format!("expected \"host\" or \"client\", got {:?}", other)),
+    };
+
+    Ok(Some(NetplayArgs {
+        bind_addr,
+        remote_addr,
+        host,
+    }))
 }
@@ -0,0 +1,31 @@
+/// A single frame's worth of paddle input. Small and `Copy` so it can be
+/// packed into a UDP datagram and kept around cheaply in per-frame history.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct NetInput {
+    pub up: bool,
+    pub down: bool,
+}
+
+impl NetInput {
+    pub fn to_byte(self) -> u8 {
+        (self.up as u8) | ((self.down as u8) << 1)
+    }
+
+    pub fn from_byte(byte: u8) -> Self {
+        NetInput {
+            up: byte & 0b01 != 0,
+            down: byte & 0b10 != 0,
+        }
+    }
+
+    /// Collapses to the same [-1, 1] axis value `PaddleSystem` reads from
+    /// the `InputHandler`, so the deterministic simulation can reuse the
+    /// existing paddle-speed feel.
+    pub fn axis(self) -> f32 {
+        match (self.up, self.down) {
+            (true, false) => 1.0,
+            (false, true) => -1.0,
+            _ => 0.0,
+        }
+    }
+}
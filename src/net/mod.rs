@@ -0,0 +1,19 @@
+mod input;
+mod session;
+mod snapshot;
+mod state;
+
+pub use self::input::NetInput;
+pub use self::session::{NetSession, INPUT_DELAY_FRAMES, MAX_PREDICTION_FRAMES};
+pub use self::snapshot::{SnapshotBuffer, WorldSnapshot};
+pub use self::state::NetPongState;
+
+/// Fixed simulation step used for the whole networked path (60 Hz) so both
+/// peers advance identically regardless of frame-rate or wall-clock jitter.
+pub const FIXED_DT: f32 = 1.0 / 60.0;
+
+/// Checked by `PaddleSystem`, `MoveBallsSystem` and `BounceSystem` so they
+/// stand down while `NetPongState` drives its own fixed-step, rollback-aware
+/// simulation over the same paddle/ball entities.
+#[derive(Default)]
+pub struct NetplayActive(pub bool);
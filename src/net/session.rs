@@ -0,0 +1,92 @@
+use std::collections::HashMap;
+use std::net::{SocketAddr, UdpSocket};
+
+use crate::pong::Side;
+
+use super::input::NetInput;
+
+/// How many frames ahead of `current_frame` we send our local input for.
+/// Gives the packet time to arrive before the remote peer needs it,
+/// trading a small amount of added latency for fewer rollbacks.
+pub const INPUT_DELAY_FRAMES: u32 = 2;
+
+/// How far `current_frame` may run ahead of the last confirmed remote frame
+/// before the session stalls rather than predicting indefinitely.
+pub const MAX_PREDICTION_FRAMES: u32 = 10;
+
+/// One UDP packet: a frame number followed by the input for that frame.
+const PACKET_LEN: usize = 9;
+
+/// Owns the socket and per-frame input history for a two-player netplay
+/// session. `NetPongState` is the only thing that touches this; it is not
+/// inserted as a `World` resource because a raw socket isn't `Sync`.
+pub struct NetSession {
+    socket: UdpSocket,
+    pub local_player: Side,
+    pub current_frame: u64,
+    pub confirmed_remote_frame: u64,
+    pub local_inputs: HashMap<u64, NetInput>,
+    pub remote_inputs: HashMap<u64, NetInput>,
+    last_known_remote_input: NetInput,
+}
+
+impl NetSession {
+    pub fn connect(
+        bind_addr: SocketAddr,
+        remote_addr: SocketAddr,
+        local_player: Side,
+    ) -> std::io::Result<Self> {
+        let socket = UdpSocket::bind(bind_addr)?;
+        socket.set_nonblocking(true)?;
+        socket.connect(remote_addr)?;
+
+        Ok(NetSession {
+            socket,
+            local_player,
+            current_frame: 0,
+            confirmed_remote_frame: 0,
+            local_inputs: HashMap::new(),
+            remote_inputs: HashMap::new(),
+            last_known_remote_input: NetInput::default(),
+        })
+    }
+
+    pub fn send_input(&self, frame: u64, input: NetInput) {
+        let mut packet = [0u8; PACKET_LEN];
+        packet[0..8].copy_from_slice(&frame.to_le_bytes());
+        packet[8] = input.to_byte();
+        // Best-effort; a dropped packet is recovered by a later resend of
+        // the same delayed frame or by prediction on the remote side.
+        let _ = self.socket.send(&packet);
+    }
+
+    /// Drains any input packets the remote peer has sent since the last
+    /// call, recording them in `remote_inputs`.
+    pub fn poll_remote_inputs(&mut self) {
+        let mut buf = [0u8; PACKET_LEN];
+        loop {
+            match self.socket.recv(&mut buf) {
+                Ok(n) if n == PACKET_LEN => {
+                    let frame = u64::from_le_bytes(buf[0..8].try_into().unwrap());
+                    let input = NetInput::from_byte(buf[8]);
+                    self.remote_inputs.insert(frame, input);
+                    if frame > self.confirmed_remote_frame {
+                        self.confirmed_remote_frame = frame;
+                    }
+                }
+                _ => break,
+            }
+        }
+    }
+
+    /// The remote input to use for `frame`: the confirmed value if it has
+    /// arrived, otherwise the last input we did receive, predicted forward.
+    pub fn remote_input_for(&mut self, frame: u64) -> (NetInput, bool) {
+        if let Some(&input) = self.remote_inputs.get(&frame) {
+            self.last_known_remote_input = input;
+            (input, true)
+        } else {
+            (self.last_known_remote_input, false)
+        }
+    }
+}
@@ -0,0 +1,44 @@
+/// Everything the deterministic simulation needs to resume from an
+/// arbitrary frame: ball position/velocity, both paddle Y positions, and
+/// both scores.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct WorldSnapshot {
+    pub frame: u64,
+    pub ball_pos: [f32; 2],
+    pub ball_vel: [f32; 2],
+    pub left_paddle_y: f32,
+    pub right_paddle_y: f32,
+    pub score_left: i32,
+    pub score_right: i32,
+}
+
+/// How many frames of history to retain. Must comfortably exceed
+/// `MAX_PREDICTION_FRAMES`, since a rollback restores from a snapshot that
+/// old.
+const HISTORY_LEN: usize = 32;
+
+/// A ring buffer of `WorldSnapshot`s keyed by frame number.
+pub struct SnapshotBuffer {
+    slots: Vec<Option<WorldSnapshot>>,
+}
+
+impl Default for SnapshotBuffer {
+    fn default() -> Self {
+        SnapshotBuffer {
+            slots: vec![None; HISTORY_LEN],
+        }
+    }
+}
+
+impl SnapshotBuffer {
+    pub fn store(&mut self, snapshot: WorldSnapshot) {
+        let idx = (snapshot.frame as usize) % HISTORY_LEN;
+        self.slots[idx] = Some(snapshot);
+    }
+
+    /// Returns the snapshot for `frame`, or `None` if it has already been
+    /// evicted (older than `HISTORY_LEN` frames).
+    pub fn get(&self, frame: u64) -> Option<WorldSnapshot> {
+        self.slots[(frame as usize) % HISTORY_LEN].filter(|s| s.frame == frame)
+    }
+}
@@ -0,0 +1,438 @@
+use std::collections::HashMap;
+use std::net::SocketAddr;
+
+use amethyst::assets::Handle;
+use amethyst::core::timing::Time;
+use amethyst::core::Transform;
+use amethyst::ecs::{Entity, Join, Read, ReadExpect, ReadStorage, Write, WriteStorage};
+use amethyst::input::{is_close_requested, InputHandler, StringBindings};
+use amethyst::prelude::*;
+use amethyst::renderer::SpriteSheet;
+use amethyst::ui::UiText;
+
+use crate::audio::{initialize_audio, select_soundtrack};
+use crate::pong::{init, Ball, Paddle, ScoreBoard, ScoreText, Side, ARENA_HEIGHT, ARENA_WIDTH};
+use crate::states::Paused;
+
+use super::{NetInput, NetSession, NetplayActive, SnapshotBuffer, WorldSnapshot, FIXED_DT};
+
+/// Frame on which the ball spawns, replacing `Pong`'s wall-clock
+/// `ball_spawn_timer` with something that derives from the frame counter so
+/// both peers spawn it on the exact same simulated frame.
+const BALL_SPAWN_FRAME: u64 = (2.0 / FIXED_DT) as u64;
+
+/// Two-player netplay over UDP with input-delay + rollback. Mirrors
+/// `Pong`'s entity setup but drives paddles and the ball through its own
+/// deterministic, fixed-step simulation instead of the wall-clock
+/// `PaddleSystem`/`MoveBallsSystem`/`BounceSystem` trio (which stand down
+/// for the duration via the `NetplayActive` flag).
+pub struct NetPongState {
+    bind_addr: SocketAddr,
+    remote_addr: SocketAddr,
+    local_player: Side,
+    entities: Vec<Entity>,
+    sprite_sheet_handle: Option<Handle<SpriteSheet>>,
+    session: Option<NetSession>,
+    snapshots: SnapshotBuffer,
+    accumulator: f32,
+    ball_spawned: bool,
+    /// The remote `NetInput` actually used to simulate each already-run
+    /// frame (confirmed if it had arrived by then, predicted otherwise).
+    /// Compared against `NetSession::remote_inputs` in `reconcile` to tell
+    /// a genuine misprediction from an input that merely arrived late but
+    /// matched the prediction.
+    predicted_remote_inputs: HashMap<u64, NetInput>,
+    /// Frames up to this one have already been checked for mispredictions,
+    /// so `reconcile` only has to scan forward from here instead of
+    /// rescanning the whole confirmed history every frame.
+    verified_frame: u64,
+}
+
+impl NetPongState {
+    pub fn new(bind_addr: SocketAddr, remote_addr: SocketAddr, local_player: Side) -> Self {
+        NetPongState {
+            bind_addr,
+            remote_addr,
+            local_player,
+            entities: Vec::new(),
+            sprite_sheet_handle: None,
+            session: None,
+            snapshots: SnapshotBuffer::default(),
+            accumulator: 0.0,
+            ball_spawned: false,
+            predicted_remote_inputs: HashMap::new(),
+            verified_frame: 0,
+        }
+    }
+
+    fn advance_one_frame(&mut self, world: &mut World) {
+        let local_input = read_local_input(world, self.local_player);
+
+        let session = self
+            .session
+            .as_mut()
+            .expect("advance_one_frame called without a session");
+
+        // Stall rather than predict further once we've run too far ahead of
+        // what the remote peer has actually confirmed.
+        if session
+            .current_frame
+            .saturating_sub(session.confirmed_remote_frame)
+            > u64::from(MAX_STALL_FRAMES)
+        {
+            return;
+        }
+
+        let frame = session.current_frame;
+
+        // Buffer the local input by the same `INPUT_DELAY_FRAMES` the
+        // remote peer experiences receiving it over the network, so both
+        // sides apply every input on the same simulated frame instead of
+        // the local peer reacting to itself instantly.
+        let input_frame = frame + u64::from(super::INPUT_DELAY_FRAMES);
+        session.local_inputs.insert(input_frame, local_input);
+        session.send_input(input_frame, local_input);
+        let local_input = session
+            .local_inputs
+            .get(&frame)
+            .copied()
+            .unwrap_or_default();
+
+        let (remote_input, _confirmed) = session.remote_input_for(frame);
+        self.predicted_remote_inputs.insert(frame, remote_input);
+        let (left_input, right_input) = match self.local_player {
+            Side::Left => (local_input, remote_input),
+            Side::Right => (remote_input, local_input),
+        };
+
+        self.snapshots.store(capture_snapshot(world, frame));
+
+        if !self.ball_spawned && frame == BALL_SPAWN_FRAME {
+            let sprite_sheet_handle = self.sprite_sheet_handle.clone().unwrap();
+            self.entities.push(init::ball(world, sprite_sheet_handle));
+            self.ball_spawned = true;
+        }
+        if self.ball_spawned {
+            simulate_frame(world, left_input, right_input, FIXED_DT);
+        }
+
+        self.session.as_mut().unwrap().current_frame += 1;
+        self.reconcile(world);
+    }
+
+    /// Scans newly-confirmed remote inputs for the earliest frame whose
+    /// confirmed value contradicts what was predicted when that frame was
+    /// actually simulated; if one is found, restores the snapshot from
+    /// that frame and re-simulates forward to the present with the
+    /// now-confirmed inputs. Frames whose predictions already matched are
+    /// left alone instead of being re-simulated for nothing.
+    fn reconcile(&mut self, world: &mut World) {
+        let (present, confirmed_remote_frame) = {
+            let session = self.session.as_ref().unwrap();
+            (session.current_frame, session.confirmed_remote_frame)
+        };
+
+        let mut contradicted = None;
+        {
+            let session = self.session.as_ref().unwrap();
+            let mut frame = self.verified_frame;
+            while frame < confirmed_remote_frame {
+                if let Some(&confirmed) = session.remote_inputs.get(&frame) {
+                    let predicted = self
+                        .predicted_remote_inputs
+                        .get(&frame)
+                        .copied()
+                        .unwrap_or_default();
+                    if predicted != confirmed {
+                        contradicted = Some(frame);
+                        break;
+                    }
+                }
+                frame += 1;
+            }
+        }
+        self.verified_frame = confirmed_remote_frame;
+
+        let mut frame = match contradicted {
+            Some(frame) => frame,
+            None => return,
+        };
+        if frame >= present {
+            return;
+        }
+
+        let snapshot = match self.snapshots.get(frame) {
+            Some(snapshot) => snapshot,
+            None => return,
+        };
+        restore_snapshot(world, &snapshot);
+
+        while frame < present {
+            let session = self.session.as_mut().unwrap();
+            let local_input = session
+                .local_inputs
+                .get(&frame)
+                .copied()
+                .unwrap_or_default();
+            let (remote_input, _) = session.remote_input_for(frame);
+            self.predicted_remote_inputs.insert(frame, remote_input);
+            let (left_input, right_input) = match self.local_player {
+                Side::Left => (local_input, remote_input),
+                Side::Right => (remote_input, local_input),
+            };
+
+            self.snapshots.store(capture_snapshot(world, frame));
+            if self.ball_spawned && frame >= BALL_SPAWN_FRAME {
+                simulate_frame(world, left_input, right_input, FIXED_DT);
+            }
+            frame += 1;
+        }
+    }
+}
+
+/// How many frames `current_frame` may run ahead of the confirmed remote
+/// frame before play pauses to wait on the network.
+const MAX_STALL_FRAMES: u32 = super::MAX_PREDICTION_FRAMES;
+
+impl SimpleState for NetPongState {
+    fn on_start(&mut self, data: StateData<'_, GameData<'_, '_>>) {
+        let world = data.world;
+
+        world.insert(Paused(false));
+        world.insert(NetplayActive(true));
+        world.insert(ScoreBoard::default());
+
+        let sprite_sheet_handle = init::load_sprite_sheet(world);
+        self.entities
+            .extend(init::paddles(world, sprite_sheet_handle.clone()));
+        self.sprite_sheet_handle = Some(sprite_sheet_handle);
+        self.entities.extend(init::scoreboard(world));
+        self.entities.push(init::camera(world));
+        initialize_audio(world);
+        select_soundtrack(world, "gameplay");
+
+        match NetSession::connect(self.bind_addr, self.remote_addr, self.local_player) {
+            Ok(session) => self.session = Some(session),
+            Err(err) => eprintln!("Failed to start netplay session: {}", err),
+        }
+    }
+
+    fn on_stop(&mut self, data: StateData<'_, GameData<'_, '_>>) {
+        let world = data.world;
+        world.write_resource::<NetplayActive>().0 = false;
+        world
+            .delete_entities(&self.entities)
+            .expect("Failed to delete NetPong entities");
+        self.entities.clear();
+        self.session = None;
+        self.ball_spawned = false;
+    }
+
+    fn handle_event(
+        &mut self,
+        _data: StateData<'_, GameData<'_, '_>>,
+        event: StateEvent,
+    ) -> SimpleTrans {
+        if let StateEvent::Window(event) = &event {
+            if is_close_requested(event) {
+                return Trans::Quit;
+            }
+        }
+
+        Trans::None
+    }
+
+    fn update(&mut self, data: &mut StateData<'_, GameData<'_, '_>>) -> SimpleTrans {
+        if self.session.is_none() {
+            return Trans::None;
+        }
+
+        let world = data.world;
+        self.session.as_mut().unwrap().poll_remote_inputs();
+
+        let dt = world.fetch::<Time>().delta_seconds();
+        self.accumulator += dt;
+
+        while self.accumulator >= FIXED_DT {
+            self.accumulator -= FIXED_DT;
+            self.advance_one_frame(world);
+        }
+
+        Trans::None
+    }
+}
+
+fn read_local_input(world: &World, local_player: Side) -> NetInput {
+    let input = world.fetch::<InputHandler<StringBindings>>();
+    let axis_name = match local_player {
+        Side::Left => "left_paddle",
+        Side::Right => "right_paddle",
+    };
+    match input.axis_value(axis_name) {
+        Some(value) if value > 0.0 => NetInput {
+            up: true,
+            down: false,
+        },
+        Some(value) if value < 0.0 => NetInput {
+            up: false,
+            down: true,
+        },
+        _ => NetInput::default(),
+    }
+}
+
+fn capture_snapshot(world: &World, frame: u64) -> WorldSnapshot {
+    world.exec(
+        |(balls, paddles, transforms, scores): (
+            ReadStorage<Ball>,
+            ReadStorage<Paddle>,
+            ReadStorage<Transform>,
+            Read<ScoreBoard>,
+        )| {
+            let mut snapshot = WorldSnapshot {
+                frame,
+                score_left: scores.score_left,
+                score_right: scores.score_right,
+                ..Default::default()
+            };
+
+            for (ball, transform) in (&balls, &transforms).join() {
+                snapshot.ball_pos = [transform.translation().x, transform.translation().y];
+                snapshot.ball_vel = ball.velocity;
+            }
+
+            for (paddle, transform) in (&paddles, &transforms).join() {
+                match paddle.side {
+                    Side::Left => snapshot.left_paddle_y = transform.translation().y,
+                    Side::Right => snapshot.right_paddle_y = transform.translation().y,
+                }
+            }
+
+            snapshot
+        },
+    )
+}
+
+fn restore_snapshot(world: &mut World, snapshot: &WorldSnapshot) {
+    world.exec(
+        |(mut balls, paddles, mut transforms, mut scores): (
+            WriteStorage<Ball>,
+            ReadStorage<Paddle>,
+            WriteStorage<Transform>,
+            Write<ScoreBoard>,
+        )| {
+            scores.score_left = snapshot.score_left;
+            scores.score_right = snapshot.score_right;
+
+            for (ball, transform) in (&mut balls, &mut transforms).join() {
+                ball.velocity = snapshot.ball_vel;
+                transform.set_translation_x(snapshot.ball_pos[0]);
+                transform.set_translation_y(snapshot.ball_pos[1]);
+            }
+
+            for (paddle, transform) in (&paddles, &mut transforms).join() {
+                let y = match paddle.side {
+                    Side::Left => snapshot.left_paddle_y,
+                    Side::Right => snapshot.right_paddle_y,
+                };
+                transform.set_translation_y(y);
+            }
+        },
+    );
+}
+
+/// The deterministic equivalent of `PaddleSystem` + `MoveBallsSystem` +
+/// `BounceSystem` for one fixed `dt` step, driven by confirmed/predicted
+/// `NetInput` rather than the wall-clock `InputHandler`.
+fn simulate_frame(world: &mut World, left_input: NetInput, right_input: NetInput, dt: f32) {
+    world.exec(
+        |(paddles, mut transforms): (ReadStorage<Paddle>, WriteStorage<Transform>)| {
+            for (paddle, transform) in (&paddles, &mut transforms).join() {
+                let axis = match paddle.side {
+                    Side::Left => left_input.axis(),
+                    Side::Right => right_input.axis(),
+                };
+                if axis != 0.0 {
+                    // 1.2 units/frame at 60Hz is the same paddle speed `PaddleSystem` uses.
+                    let scaled = 1.2 * axis * dt * 60.0;
+                    let paddle_y = transform.translation().y;
+                    transform.set_translation_y(
+                        (paddle_y + scaled)
+                            .min(ARENA_HEIGHT - paddle.height * 0.5)
+                            .max(paddle.height * 0.5),
+                    );
+                }
+            }
+        },
+    );
+
+    let paddle_rects: Vec<(Side, f32, f32, f32, f32)> = world.exec(
+        |(paddles, transforms): (ReadStorage<Paddle>, ReadStorage<Transform>)| {
+            (&paddles, &transforms)
+                .join()
+                .map(|(paddle, transform)| {
+                    (
+                        paddle.side,
+                        transform.translation().x - paddle.width * 0.5,
+                        transform.translation().y - paddle.height * 0.5,
+                        paddle.width,
+                        paddle.height,
+                    )
+                })
+                .collect()
+        },
+    );
+
+    world.exec(
+        |(mut balls, mut transforms, mut ui_text, mut scores, score_text): (
+            WriteStorage<Ball>,
+            WriteStorage<Transform>,
+            WriteStorage<UiText>,
+            Write<ScoreBoard>,
+            ReadExpect<ScoreText>,
+        )| {
+            for (ball, transform) in (&mut balls, &mut transforms).join() {
+                transform.prepend_translation_x(ball.velocity[0] * dt);
+                transform.prepend_translation_y(ball.velocity[1] * dt);
+
+                let ball_x = transform.translation().x;
+                let ball_y = transform.translation().y;
+
+                if (ball_y <= ball.radius && ball.velocity[1] < 0.0)
+                    || (ball_y >= ARENA_HEIGHT - ball.radius && ball.velocity[1] > 0.0)
+                {
+                    ball.velocity[1] = -ball.velocity[1];
+                }
+
+                for &(side, left, bottom, width, height) in &paddle_rects {
+                    let in_rect = ball_x >= left - ball.radius
+                        && ball_x <= left + width + ball.radius
+                        && ball_y >= bottom - ball.radius
+                        && ball_y <= bottom + height + ball.radius;
+                    if in_rect
+                        && ((side == Side::Left && ball.velocity[0] < 0.0)
+                            || (side == Side::Right && ball.velocity[0] > 0.0))
+                    {
+                        ball.velocity[0] = -ball.velocity[0];
+                    }
+                }
+
+                if ball_x <= ball.radius {
+                    scores.score_right += 1;
+                    if let Some(text) = ui_text.get_mut(score_text.p2_score) {
+                        text.text = scores.score_right.to_string();
+                    }
+                    ball.velocity[0] = -ball.velocity[0];
+                    transform.set_translation_x(ARENA_WIDTH / 2.0);
+                } else if ball_x >= ARENA_WIDTH - ball.radius {
+                    scores.score_left += 1;
+                    if let Some(text) = ui_text.get_mut(score_text.p1_score) {
+                        text.text = scores.score_left.to_string();
+                    }
+                    ball.velocity[0] = -ball.velocity[0];
+                    transform.set_translation_x(ARENA_WIDTH / 2.0);
+                }
+            }
+        },
+    );
+}
@@ -2,12 +2,16 @@ use amethyst::{
     assets::{AssetStorage, Handle, Loader},
     core::{timing::Time, transform::Transform},
     ecs::{Component, DenseVecStorage, Entity},
+    input::{is_close_requested, is_key_down, VirtualKeyCode},
     prelude::*,
     renderer::{Camera, ImageFormat, SpriteRender, SpriteSheet, SpriteSheetFormat, Texture},
     ui::{Anchor, LineMode, TtfFormat, UiText, UiTransform},
 };
 
-use crate::audio::initialize_audio;
+use crate::ai::AiPaddle;
+use crate::audio::{initialize_audio, select_soundtrack};
+use crate::settings::Settings;
+use crate::states::{GameOverState, Paused, PausedState};
 
 pub const ARENA_HEIGHT: f32 = 100.0;
 pub const ARENA_WIDTH: f32 = 100.0;
@@ -23,12 +27,18 @@ pub const BALL_RADIUS: f32 = 2.0;
 pub struct Pong {
     ball_spawn_timer: Option<f32>,
     sprite_sheet_handle: Option<Handle<SpriteSheet>>,
+    /// Entities created by this state, deleted again in `on_stop` so that
+    /// restarting the game doesn't leave stale paddles/balls/UI behind.
+    entities: Vec<Entity>,
 }
 
 impl SimpleState for Pong {
     fn on_start(&mut self, data: StateData<'_, GameData<'_, '_>>) {
         let world = data.world;
 
+        world.insert(Paused(false));
+        world.insert(ScoreBoard::default());
+
         // Wait two seconds before spawning the ball
         self.ball_spawn_timer.replace(2.0);
 
@@ -38,10 +48,37 @@ impl SimpleState for Pong {
         self.sprite_sheet_handle
             .replace(init::load_sprite_sheet(world));
 
-        init::paddles(world, self.sprite_sheet_handle.clone().unwrap());
-        init::scoreboard(world);
-        init::camera(world);
+        self.entities
+            .extend(init::paddles(world, self.sprite_sheet_handle.clone().unwrap()));
+        self.entities.extend(init::scoreboard(world));
+        self.entities.push(init::camera(world));
         initialize_audio(world);
+        select_soundtrack(world, "gameplay");
+    }
+
+    fn on_stop(&mut self, data: StateData<'_, GameData<'_, '_>>) {
+        data.world
+            .delete_entities(&self.entities)
+            .expect("Failed to delete Pong entities");
+        self.entities.clear();
+        self.ball_spawn_timer.take();
+    }
+
+    fn handle_event(
+        &mut self,
+        _data: StateData<'_, GameData<'_, '_>>,
+        event: StateEvent,
+    ) -> SimpleTrans {
+        if let StateEvent::Window(event) = &event {
+            if is_close_requested(event) {
+                return Trans::Quit;
+            }
+            if is_key_down(event, VirtualKeyCode::Escape) {
+                return Trans::Push(Box::new(PausedState::default()));
+            }
+        }
+
+        Trans::None
     }
 
     fn update(&mut self, data: &mut StateData<'_, GameData<'_, '_>>) -> SimpleTrans {
@@ -54,21 +91,31 @@ impl SimpleState for Pong {
 
             if timer <= 0.0 {
                 // When timer has expired, spawn the ball
-                init::ball(data.world, self.sprite_sheet_handle.clone().unwrap());
+                self.entities
+                    .push(init::ball(data.world, self.sprite_sheet_handle.clone().unwrap()));
             } else {
                 // If timer hasn't expired, put it back into state
                 self.ball_spawn_timer.replace(timer);
             }
         }
 
+        let score_to_win = data.world.fetch::<Settings>().score_to_win;
+        let scores = data.world.fetch::<ScoreBoard>();
+        if scores.score_left >= score_to_win {
+            return Trans::Switch(Box::new(GameOverState::new(Side::Left)));
+        }
+        if scores.score_right >= score_to_win {
+            return Trans::Switch(Box::new(GameOverState::new(Side::Right)));
+        }
+
         Trans::None
     }
 }
 
-mod init {
+pub(crate) mod init {
     use super::*;
 
-    pub fn camera(world: &mut World) {
+    pub fn camera(world: &mut World) -> Entity {
         let mut transform = Transform::default();
         transform.set_translation_xyz(ARENA_WIDTH * 0.5, ARENA_HEIGHT * 0.5, 1.0);
 
@@ -76,7 +123,7 @@ mod init {
             .create_entity()
             .with(Camera::standard_2d(ARENA_WIDTH, ARENA_HEIGHT))
             .with(transform)
-            .build();
+            .build()
     }
 
     pub fn load_sprite_sheet(world: &mut World) -> Handle<SpriteSheet> {
@@ -101,7 +148,9 @@ mod init {
         )
     }
 
-    pub fn paddles(world: &mut World, sprite_sheet_handle: Handle<SpriteSheet>) {
+    pub fn paddles(world: &mut World, sprite_sheet_handle: Handle<SpriteSheet>) -> [Entity; 2] {
+        let game_mode = *world.fetch::<GameMode>();
+
         let sprite_render = SpriteRender::new(sprite_sheet_handle, 0);
         let mut left_transform = Transform::default();
         let mut right_transform = Transform::default();
@@ -110,22 +159,27 @@ mod init {
         left_transform.set_translation_xyz(PADDLE_WIDTH * 0.5, y, 0.0);
         right_transform.set_translation_xyz(ARENA_WIDTH - PADDLE_WIDTH * 0.5, y, 0.0);
 
-        world
+        let left = world
             .create_entity()
             .with(sprite_render.clone())
             .with(Paddle::new(Side::Left))
             .with(left_transform)
             .build();
 
-        world
+        let mut right_builder = world
             .create_entity()
             .with(sprite_render)
             .with(Paddle::new(Side::Right))
-            .with(right_transform)
-            .build();
+            .with(right_transform);
+        if game_mode == GameMode::OnePlayer {
+            right_builder = right_builder.with(AiPaddle);
+        }
+        let right = right_builder.build();
+
+        [left, right]
     }
 
-    pub fn ball(world: &mut World, sprite_sheet_handle: Handle<SpriteSheet>) {
+    pub fn ball(world: &mut World, sprite_sheet_handle: Handle<SpriteSheet>) -> Entity {
         // Create a translation object
         let mut local_transform = Transform::default();
         local_transform.set_translation_xyz(ARENA_WIDTH / 2.0, ARENA_HEIGHT / 2.0, 0.0);
@@ -141,10 +195,10 @@ mod init {
                 velocity: [BALL_VELOCITY_X, BALL_VELOCITY_Y],
             })
             .with(local_transform)
-            .build();
+            .build()
     }
 
-    pub fn scoreboard(world: &mut World) {
+    pub fn scoreboard(world: &mut World) -> [Entity; 2] {
         let font = world.read_resource::<Loader>().load(
             "font/square.ttf",
             TtfFormat,
@@ -200,16 +254,33 @@ mod init {
             ))
             .build();
 
-        world.insert(ScoreText { p1_score, p2_score })
+        world.insert(ScoreText { p1_score, p2_score });
+
+        [p1_score, p2_score]
     }
 }
 
-#[derive(PartialEq, Eq)]
+#[derive(Clone, Copy, PartialEq, Eq)]
 pub enum Side {
     Left,
     Right,
 }
 
+/// Whether `Side::Right` is driven by a human via input bindings or by
+/// `AiPaddleSystem`. Chosen on the main menu and read once by
+/// `init::paddles` when `Pong` starts.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum GameMode {
+    OnePlayer,
+    TwoPlayer,
+}
+
+impl Default for GameMode {
+    fn default() -> Self {
+        GameMode::TwoPlayer
+    }
+}
+
 pub struct Paddle {
     pub side: Side,
     pub width: f32,
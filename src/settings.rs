@@ -0,0 +1,63 @@
+use std::path::{Path, PathBuf};
+
+use amethyst::config::Config;
+use serde::{Deserialize, Serialize};
+
+use crate::ai::AiDifficulty;
+use crate::display::DisplaySettings;
+
+/// Filesystem path `settings.ron` was loaded from, inserted as its own
+/// `World` resource (rather than a field on `Settings`, which is itself
+/// serialized) so any system that mutates `Settings` at runtime can save
+/// it back without needing the path threaded in separately.
+pub struct SettingsPath(pub PathBuf);
+
+/// User-tunable game settings, persisted to `settings.ron` next to the
+/// other `config/*.ron` files. Inserted as a `World` resource at launch;
+/// call [`Settings::save`] with the path from [`SettingsPath`] after
+/// mutating a field so the change survives to the next run.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct Settings {
+    pub music_volume: f32,
+    pub sfx_volume: f32,
+    pub score_to_win: i32,
+    pub display: DisplaySettings,
+    pub ai_difficulty: AiDifficulty,
+}
+
+impl Default for Settings {
+    fn default() -> Self {
+        Settings {
+            music_volume: 0.25,
+            sfx_volume: 1.0,
+            score_to_win: 3,
+            display: DisplaySettings::default(),
+            ai_difficulty: AiDifficulty::default(),
+        }
+    }
+}
+
+impl Settings {
+    /// Loads `settings.ron` from `path`. If it doesn't exist yet (e.g. on
+    /// first launch) the defaults are written out so the game configures
+    /// itself instead of erroring.
+    pub fn load_or_default(path: impl AsRef<Path>) -> Self {
+        let path = path.as_ref();
+        match Config::load(path) {
+            Ok(settings) => settings,
+            Err(_) => {
+                let settings = Settings::default();
+                settings.save(path);
+                settings
+            }
+        }
+    }
+
+    /// Rewrites `settings.ron` at `path` with the current values.
+    pub fn save(&self, path: impl AsRef<Path>) {
+        if let Err(err) = self.write(path.as_ref()) {
+            eprintln!("Failed to save settings.ron: {}", err);
+        }
+    }
+}
@@ -0,0 +1,240 @@
+use amethyst::{
+    assets::Loader,
+    ecs::Entity,
+    input::{is_close_requested, is_key_down, VirtualKeyCode},
+    prelude::*,
+    ui::{Anchor, LineMode, TtfFormat, UiText, UiTransform},
+    window::Window,
+};
+
+use crate::audio::{initialize_audio, select_soundtrack};
+use crate::display::apply_fullscreen;
+use crate::pong::{GameMode, Pong, ScoreBoard, Side};
+use crate::settings::Settings;
+
+/// Checked by `PaddleSystem`, `MoveBallsSystem` and `BounceSystem` every
+/// frame so that pushing `PausedState` on top of `Pong` freezes the
+/// simulation in place instead of tearing down any entities.
+#[derive(Default)]
+pub struct Paused(pub bool);
+
+fn create_ui_text(world: &mut World, id: &str, text: &str, y_offset: f32, font_size: f32) -> Entity {
+    let font = world.read_resource::<Loader>().load(
+        "font/square.ttf",
+        TtfFormat,
+        (),
+        &world.read_resource(),
+    );
+
+    let transform = UiTransform::new(
+        id.to_string(),
+        Anchor::Middle,
+        Anchor::Middle,
+        0.,
+        y_offset,
+        1.,
+        400.,
+        50.,
+    );
+
+    world
+        .create_entity()
+        .with(transform)
+        .with(UiText::new(
+            font,
+            text.to_string(),
+            [1., 1., 1., 1.],
+            font_size,
+            LineMode::Single,
+            Anchor::Middle,
+        ))
+        .build()
+}
+
+/// The title screen. Shown on launch and after returning from a finished
+/// game; transitions into `Pong` once the player presses Space.
+#[derive(Default)]
+pub struct MainMenuState {
+    ui_entities: Vec<Entity>,
+}
+
+impl SimpleState for MainMenuState {
+    fn on_start(&mut self, data: StateData<'_, GameData<'_, '_>>) {
+        let world = data.world;
+
+        // `Music`/`Sounds` aren't World resources until the first
+        // `initialize_audio` call; the menu is the game's entry point, so
+        // it's the one that creates them.
+        initialize_audio(world);
+        select_soundtrack(world, "menu");
+
+        // The window opens windowed regardless of the last saved choice
+        // (`DisplayConfig` has no fullscreen field to set that up-front),
+        // so the menu - also the entry point - re-applies it once here.
+        if world.fetch::<Settings>().display.fullscreen {
+            apply_fullscreen(&world.fetch::<Window>(), true);
+        }
+
+        self.ui_entities.push(create_ui_text(
+            world,
+            "title",
+            "PONG",
+            40.,
+            75.,
+        ));
+        self.ui_entities.push(create_ui_text(
+            world,
+            "prompt_1p",
+            "Press 1 for Single Player",
+            -20.,
+            24.,
+        ));
+        self.ui_entities.push(create_ui_text(
+            world,
+            "prompt_2p",
+            "Press 2 for Two Player",
+            -55.,
+            24.,
+        ));
+    }
+
+    fn on_stop(&mut self, data: StateData<'_, GameData<'_, '_>>) {
+        data.world
+            .delete_entities(&self.ui_entities)
+            .expect("Failed to delete main menu UI");
+        self.ui_entities.clear();
+    }
+
+    fn handle_event(
+        &mut self,
+        data: StateData<'_, GameData<'_, '_>>,
+        event: StateEvent,
+    ) -> SimpleTrans {
+        if let StateEvent::Window(event) = &event {
+            if is_close_requested(event) {
+                return Trans::Quit;
+            }
+            if is_key_down(event, VirtualKeyCode::Key1) {
+                data.world.insert(GameMode::OnePlayer);
+                return Trans::Switch(Box::new(Pong::default()));
+            }
+            if is_key_down(event, VirtualKeyCode::Key2) || is_key_down(event, VirtualKeyCode::Space)
+            {
+                data.world.insert(GameMode::TwoPlayer);
+                return Trans::Switch(Box::new(Pong::default()));
+            }
+        }
+
+        Trans::None
+    }
+}
+
+/// Pushed on top of `Pong` when the player presses Escape during play.
+/// Gameplay systems keep running their dispatcher but bail out immediately
+/// because of the shared `Paused` resource, so the world underneath is
+/// frozen rather than cleaned up.
+#[derive(Default)]
+pub struct PausedState {
+    ui_entities: Vec<Entity>,
+}
+
+impl SimpleState for PausedState {
+    fn on_start(&mut self, data: StateData<'_, GameData<'_, '_>>) {
+        let world = data.world;
+        world.write_resource::<Paused>().0 = true;
+        self.ui_entities
+            .push(create_ui_text(world, "paused", "Paused", 0., 60.));
+    }
+
+    fn on_stop(&mut self, data: StateData<'_, GameData<'_, '_>>) {
+        let world = data.world;
+        world.write_resource::<Paused>().0 = false;
+        world
+            .delete_entities(&self.ui_entities)
+            .expect("Failed to delete paused overlay UI");
+        self.ui_entities.clear();
+    }
+
+    fn handle_event(
+        &mut self,
+        _data: StateData<'_, GameData<'_, '_>>,
+        event: StateEvent,
+    ) -> SimpleTrans {
+        if let StateEvent::Window(event) = &event {
+            if is_close_requested(event) {
+                return Trans::Quit;
+            }
+            if is_key_down(event, VirtualKeyCode::Escape) {
+                return Trans::Pop;
+            }
+        }
+
+        Trans::None
+    }
+}
+
+/// Shown once a player reaches the winning score. `Pong::update` is
+/// responsible for noticing the score threshold and `Trans::Switch`-ing
+/// here, since only a `SimpleState` can change the state stack.
+pub struct GameOverState {
+    winner: Side,
+    ui_entities: Vec<Entity>,
+}
+
+impl GameOverState {
+    pub fn new(winner: Side) -> Self {
+        GameOverState {
+            winner,
+            ui_entities: Vec::new(),
+        }
+    }
+}
+
+impl SimpleState for GameOverState {
+    fn on_start(&mut self, data: StateData<'_, GameData<'_, '_>>) {
+        let world = data.world;
+
+        select_soundtrack(world, "game_over");
+
+        let winner_text = match self.winner {
+            Side::Left => "Player 1 Wins!",
+            Side::Right => "Player 2 Wins!",
+        };
+        self.ui_entities
+            .push(create_ui_text(world, "winner", winner_text, 40., 60.));
+        self.ui_entities.push(create_ui_text(
+            world,
+            "restart_prompt",
+            "Press Space to Restart",
+            -40.,
+            30.,
+        ));
+
+        world.write_resource::<ScoreBoard>().score_left = 0;
+        world.write_resource::<ScoreBoard>().score_right = 0;
+    }
+
+    fn on_stop(&mut self, data: StateData<'_, GameData<'_, '_>>) {
+        data.world
+            .delete_entities(&self.ui_entities)
+            .expect("Failed to delete game over UI");
+        self.ui_entities.clear();
+    }
+
+    fn handle_event(
+        &mut self,
+        _data: StateData<'_, GameData<'_, '_>>,
+        event: StateEvent,
+    ) -> SimpleTrans {
+        if let StateEvent::Window(event) = &event {
+            if is_close_requested(event) {
+                return Trans::Quit;
+            }
+            if is_key_down(event, VirtualKeyCode::Space) {
+                return Trans::Switch(Box::new(Pong::default()));
+            }
+        }
+
+        Trans::None
+    }
+}
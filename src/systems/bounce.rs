@@ -0,0 +1,73 @@
+use amethyst::assets::AssetStorage;
+use amethyst::audio::{output::Output, Source};
+use amethyst::core::Transform;
+use amethyst::ecs::{Join, Read, ReadExpect, ReadStorage, System, WriteStorage};
+
+use crate::audio::{play_bounce_sound, Sounds};
+use crate::net::NetplayActive;
+use crate::pong::{Ball, Paddle, Side};
+use crate::settings::Settings;
+use crate::states::Paused;
+
+/// Bounces balls off the arena walls and paddles.
+pub struct BounceSystem;
+
+impl<'s> System<'s> for BounceSystem {
+    type SystemData = (
+        WriteStorage<'s, Ball>,
+        ReadStorage<'s, Paddle>,
+        ReadStorage<'s, Transform>,
+        Read<'s, AssetStorage<Source>>,
+        ReadExpect<'s, Sounds>,
+        Option<Read<'s, Output>>,
+        Read<'s, Paused>,
+        Read<'s, NetplayActive>,
+        Read<'s, Settings>,
+    );
+
+    fn run(
+        &mut self,
+        (mut balls, paddles, transforms, storage, sounds, audio_output, paused, netplay, settings): Self::SystemData,
+    ) {
+        if paused.0 || netplay.0 {
+            return;
+        }
+
+        let sfx_volume = settings.sfx_volume;
+
+        for (ball, transform) in (&mut balls, &transforms).join() {
+            let ball_x = transform.translation().x;
+            let ball_y = transform.translation().y;
+
+            if (ball_y <= ball.radius && ball.velocity[1] < 0.0)
+                || (ball_y >= crate::pong::ARENA_HEIGHT - ball.radius && ball.velocity[1] > 0.0)
+            {
+                ball.velocity[1] = -ball.velocity[1];
+                play_bounce_sound(&*sounds, &storage, audio_output.as_deref(), sfx_volume);
+            }
+
+            for (paddle, paddle_transform) in (&paddles, &transforms).join() {
+                let paddle_x = paddle_transform.translation().x - (paddle.width * 0.5);
+                let paddle_y = paddle_transform.translation().y - (paddle.height * 0.5);
+
+                if point_in_rect(
+                    ball_x,
+                    ball_y,
+                    paddle_x - ball.radius,
+                    paddle_y - ball.radius,
+                    paddle_x + paddle.width + ball.radius,
+                    paddle_y + paddle.height + ball.radius,
+                ) && ((paddle.side == Side::Left && ball.velocity[0] < 0.0)
+                    || (paddle.side == Side::Right && ball.velocity[0] > 0.0))
+                {
+                    ball.velocity[0] = -ball.velocity[0];
+                    play_bounce_sound(&*sounds, &storage, audio_output.as_deref(), sfx_volume);
+                }
+            }
+        }
+    }
+}
+
+fn point_in_rect(x: f32, y: f32, left: f32, bottom: f32, right: f32, top: f32) -> bool {
+    x >= left && x <= right && y >= bottom && y <= top
+}
@@ -0,0 +1,9 @@
+mod bounce;
+mod move_balls;
+mod paddle;
+mod winner;
+
+pub use self::bounce::BounceSystem;
+pub use self::move_balls::MoveBallsSystem;
+pub use self::paddle::PaddleSystem;
+pub use self::winner::WinnerSystem;
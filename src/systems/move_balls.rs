@@ -0,0 +1,30 @@
+use amethyst::core::{timing::Time, Transform};
+use amethyst::ecs::{Join, Read, ReadStorage, System, WriteStorage};
+
+use crate::net::NetplayActive;
+use crate::pong::Ball;
+use crate::states::Paused;
+
+/// Integrates each ball's velocity into its position every frame.
+pub struct MoveBallsSystem;
+
+impl<'s> System<'s> for MoveBallsSystem {
+    type SystemData = (
+        ReadStorage<'s, Ball>,
+        WriteStorage<'s, Transform>,
+        Read<'s, Time>,
+        Read<'s, Paused>,
+        Read<'s, NetplayActive>,
+    );
+
+    fn run(&mut self, (balls, mut locals, time, paused, netplay): Self::SystemData) {
+        if paused.0 || netplay.0 {
+            return;
+        }
+
+        for (ball, local) in (&balls, &mut locals).join() {
+            local.prepend_translation_x(ball.velocity[0] * time.delta_seconds());
+            local.prepend_translation_y(ball.velocity[1] * time.delta_seconds());
+        }
+    }
+}
@@ -0,0 +1,56 @@
+use amethyst::core::Transform;
+use amethyst::ecs::{Entities, Join, Read, ReadStorage, System, WriteStorage};
+use amethyst::input::{InputHandler, StringBindings};
+
+use crate::ai::AiPaddle;
+use crate::net::NetplayActive;
+use crate::pong::{Paddle, Side, ARENA_HEIGHT};
+use crate::states::Paused;
+
+/// Moves the paddles according to the `left_paddle`/`right_paddle` input
+/// axes. Paddles tagged `AiPaddle` are skipped here and steered by
+/// `AiPaddleSystem` instead.
+pub struct PaddleSystem;
+
+impl<'s> System<'s> for PaddleSystem {
+    type SystemData = (
+        Entities<'s>,
+        WriteStorage<'s, Transform>,
+        ReadStorage<'s, Paddle>,
+        ReadStorage<'s, AiPaddle>,
+        Read<'s, InputHandler<StringBindings>>,
+        Read<'s, Paused>,
+        Read<'s, NetplayActive>,
+    );
+
+    fn run(
+        &mut self,
+        (entities, mut transforms, paddles, ai_paddles, input, paused, netplay): Self::SystemData,
+    ) {
+        if paused.0 || netplay.0 {
+            return;
+        }
+
+        for (entity, paddle, transform) in (&entities, &paddles, &mut transforms).join() {
+            if ai_paddles.contains(entity) {
+                continue;
+            }
+
+            let movement = match paddle.side {
+                Side::Left => input.axis_value("left_paddle"),
+                Side::Right => input.axis_value("right_paddle"),
+            };
+            if let Some(mv_amount) = movement {
+                if mv_amount != 0.0 {
+                    let scaled_amount = 1.2 * mv_amount as f32;
+                    let paddle_y = transform.translation().y;
+                    transform.set_translation_y(
+                        (paddle_y + scaled_amount)
+                            .min(ARENA_HEIGHT - paddle.height * 0.5)
+                            .max(paddle.height * 0.5),
+                    );
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,63 @@
+use amethyst::assets::AssetStorage;
+use amethyst::audio::{output::Output, Source};
+use amethyst::core::Transform;
+use amethyst::ecs::{Join, Read, ReadExpect, System, Write, WriteStorage};
+use amethyst::ui::UiText;
+
+use crate::audio::{play_score_sound, Sounds};
+use crate::pong::{Ball, ScoreBoard, ScoreText, ARENA_WIDTH};
+use crate::settings::Settings;
+
+/// Detects a ball crossing either goal line, updates the score, resets the
+/// ball to the middle of the arena and plays the scoring sound.
+pub struct WinnerSystem;
+
+impl<'s> System<'s> for WinnerSystem {
+    type SystemData = (
+        WriteStorage<'s, Ball>,
+        WriteStorage<'s, Transform>,
+        WriteStorage<'s, UiText>,
+        Write<'s, ScoreBoard>,
+        ReadExpect<'s, ScoreText>,
+        Read<'s, AssetStorage<Source>>,
+        ReadExpect<'s, Sounds>,
+        Option<Read<'s, Output>>,
+        Read<'s, Settings>,
+    );
+
+    fn run(
+        &mut self,
+        (mut balls, mut transforms, mut ui_text, mut scores, score_text, storage, sounds, audio_output, settings): Self::SystemData,
+    ) {
+        let sfx_volume = settings.sfx_volume;
+        for (ball, transform) in (&mut balls, &mut transforms).join() {
+            let ball_x = transform.translation().x;
+
+            let scored = if ball_x <= ball.radius {
+                scores.score_right += 1;
+                if let Some(text) = ui_text.get_mut(score_text.p2_score) {
+                    text.text = scores.score_right.to_string();
+                }
+                true
+            } else if ball_x >= ARENA_WIDTH - ball.radius {
+                scores.score_left += 1;
+                if let Some(text) = ui_text.get_mut(score_text.p1_score) {
+                    text.text = scores.score_left.to_string();
+                }
+                true
+            } else {
+                false
+            };
+
+            if scored {
+                ball.velocity[0] = -ball.velocity[0];
+                transform.set_translation_x(ARENA_WIDTH / 2.0);
+                play_score_sound(&sounds, &storage, audio_output.as_deref(), sfx_volume);
+                println!(
+                    "Score: | {:^3} | {:^3} |",
+                    scores.score_left, scores.score_right
+                );
+            }
+        }
+    }
+}